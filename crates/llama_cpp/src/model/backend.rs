@@ -1,13 +1,18 @@
 //! Implements the [`Backend`] and [`BackendRef`] structs for managing llama.cpp
 //! backends
 
+use std::ffi::CStr;
+use std::fmt;
+use std::os::raw::{c_char, c_void};
 use std::ptr;
+use std::sync::Arc;
 
 use std::sync::Mutex;
 use tracing::error;
 
 use llama_cpp_sys::{
-    ggml_numa_strategy, llama_backend_free, llama_backend_init, llama_log_set, llama_numa_init,
+    ggml_log_level, ggml_numa_strategy, llama_backend_free, llama_backend_init, llama_log_set,
+    llama_numa_init,
 };
 
 use crate::detail;
@@ -16,20 +21,37 @@ use crate::detail;
 /// initialisation and freeing.
 static BACKEND: Mutex<Option<(Backend, usize)>> = Mutex::new(None);
 
-/// Empty struct used to initialise and free the [llama.cpp][llama.cpp] backend when it is created
+/// The [`LogConfig`] installed by whichever [`BackendOptions`] initialised [`BACKEND`], if any.
+///
+/// Kept in its own [`Mutex`] rather than inside [`BACKEND`] because [`log_trampoline`] can be
+/// re-entered by llama.cpp while [`BACKEND`]'s lock is already held (e.g. during
+/// [`Backend::init_with_numa`] itself).
+static LOG_HANDLER: Mutex<Option<LogConfig>> = Mutex::new(None);
+
+/// Struct used to initialise and free the [llama.cpp][llama.cpp] backend when it is created and
 /// dropped respectively.
 ///
 /// [llama.cpp]: https://github.com/ggerganov/llama.cpp/
-struct Backend;
+struct Backend {
+    /// The [`NumaStrategy`] this backend was *first* initialised with. Never changes after
+    /// construction: this is the baseline [`BackendRef::with_numa`]/[`BackendRef::with_options`]
+    /// check new requests against, so that whichever strategy wins at process start stays the
+    /// documented, stable answer for the lifetime of the process, even if [`Backend::current_numa`]
+    /// is later changed by [`BackendRef::reinit_numa`].
+    init_numa: NumaStrategy,
+    /// The [`NumaStrategy`] [llama.cpp][llama.cpp] is currently running with, as of the last call
+    /// to [`BackendRef::reinit_numa`] (or `init_numa`, if that has never been called).
+    ///
+    /// [llama.cpp]: https://github.com/ggerganov/llama.cpp/
+    current_numa: NumaStrategy,
+}
 
 impl Backend {
-    fn init() -> Self {
-        Self::init_with_numa(NumaStrategy::Distribute)
-    }
-
     /// Initialises the [llama.cpp][llama.cpp] backend and sets its logger.
     ///
-    /// There should only ever be one instance of this struct at any given time.
+    /// There should only ever be one instance of this struct at any given time. Any
+    /// [`LogConfig`] to use should already be installed in [`LOG_HANDLER`] before this is
+    /// called.
     ///
     /// [llama.cpp]: https://github.com/ggerganov/llama.cpp/
     fn init_with_numa(numa: NumaStrategy) -> Self {
@@ -37,14 +59,17 @@ impl Backend {
             // SAFETY: This is only called when no models or sessions exist.
             llama_backend_init();
 
-            // TODO look into numa strategies, this should probably be part of the API
             llama_numa_init(numa.into());
 
             // SAFETY: performs a simple assignment to static variables. Should only execute once
-            // before any logs are made.
-            llama_log_set(Some(detail::llama_log_callback), ptr::null_mut());
+            // before any logs are made. `log_trampoline` reads `LOG_HANDLER` rather than relying
+            // on the user_data pointer, so passing `ptr::null_mut()` here is fine.
+            llama_log_set(Some(log_trampoline), ptr::null_mut());
+        }
+        Self {
+            init_numa: numa,
+            current_numa: numa,
         }
-        Self
     }
 }
 
@@ -54,6 +79,10 @@ impl Drop for Backend {
             // SAFETY: This is only called when no models or sessions exist.
             llama_backend_free();
         }
+        // Clear the custom handler once the last reference to this backend is gone, so a later
+        // re-initialisation doesn't inherit a stale handler. `log_trampoline` stays registered
+        // with llama.cpp and routes through the built-in `tracing` bridge until then.
+        LOG_HANDLER.lock().unwrap().take();
     }
 }
 
@@ -64,16 +93,526 @@ impl Drop for Backend {
 pub(crate) struct BackendRef {}
 
 impl BackendRef {
-    /// Creates a new reference, initialising [`BACKEND`] if necessary.
+    /// Creates a new reference, joining [`BACKEND`] with whatever [`NumaStrategy`] is already
+    /// active, or initialising it with the default ([`NumaStrategy::Distribute`]) if necessary.
+    ///
+    /// Model/session construction code that doesn't need to pick a specific [`NumaStrategy`]
+    /// should call this; code that does should call [`BackendRef::with_numa`] or
+    /// [`BackendRef::with_options`] instead, so a conflicting choice is reported rather than
+    /// silently dropped.
     pub(crate) fn new() -> Self {
+        Self::join_or_init(None, None).expect("no strategy was requested, so this cannot conflict")
+    }
+
+    /// Creates a new reference, initialising [`BACKEND`] with the given [`NumaStrategy`] if
+    /// necessary.
+    ///
+    /// Because [`BACKEND`] is a process-wide singleton, only the first call that initialises it
+    /// gets to pick the [`NumaStrategy`]. If [`BACKEND`] is already initialised with a different
+    /// strategy than `numa`, this returns [`BackendInitError::Conflict`] instead of silently
+    /// reusing the existing one.
+    pub(crate) fn with_numa(numa: NumaStrategy) -> Result<Self, BackendInitError> {
+        Self::join_or_init(Some(numa), None)
+    }
+
+    /// Creates a new reference, initialising [`BACKEND`] with the given [`BackendOptions`] if
+    /// necessary.
+    ///
+    /// See [`BackendRef::with_numa`] for the rules around conflicting [`NumaStrategy`]s; the same
+    /// rules apply here. `options.log` is only consulted the first time [`BACKEND`] is
+    /// initialised and is otherwise ignored.
+    pub(crate) fn with_options(options: BackendOptions) -> Result<Self, BackendInitError> {
+        Self::join_or_init(Some(options.numa), options.log)
+    }
+
+    /// Shared implementation behind [`BackendRef::new`], [`BackendRef::with_numa`] and
+    /// [`BackendRef::with_options`]: joins an already-initialised [`BACKEND`], or initialises it
+    /// with `numa` (defaulting to [`NumaStrategy::Distribute`]) and `log` if there wasn't one yet.
+    ///
+    /// `numa` of `None` means "no particular strategy was requested", so joining an existing
+    /// backend can never conflict in that case; `Some` is checked against the backend's
+    /// [`Backend::init_numa`] and rejected on a mismatch, and rejected outright if it's
+    /// [`NumaStrategy::Count`] (ggml's internal sentinel element-count, not a real strategy).
+    fn join_or_init(
+        numa: Option<NumaStrategy>,
+        log: Option<LogConfig>,
+    ) -> Result<Self, BackendInitError> {
+        if let Some(NumaStrategy::Count) = numa {
+            return Err(BackendInitError::InvalidStrategy(NumaStrategy::Count));
+        }
+
         let mut lock = BACKEND.lock().unwrap();
-        if let Some((_, count)) = lock.as_mut() {
+        if let Some((backend, count)) = lock.as_mut() {
+            if let Some(numa) = numa {
+                if backend.init_numa != numa {
+                    return Err(BackendInitError::Conflict(NumaStrategyConflictError {
+                        active: backend.init_numa,
+                        requested: numa,
+                    }));
+                }
+            }
             *count += 1;
         } else {
-            let _ = lock.insert((Backend::init(), 1));
+            let numa = numa.unwrap_or(NumaStrategy::Distribute);
+            *LOG_HANDLER.lock().unwrap() = log;
+            let _ = lock.insert((Backend::init_with_numa(numa), 1));
         }
 
-        Self {}
+        Ok(Self {})
+    }
+
+    /// Re-runs [llama.cpp][llama.cpp]'s NUMA initialisation for the process-wide [`Backend`] with
+    /// a new [`NumaStrategy`], without tearing down or re-creating it.
+    ///
+    /// This is a no-op if `numa` matches the strategy the backend is currently running with. It
+    /// does *not* change the baseline [`BackendRef::with_numa`]/[`BackendRef::with_options`] check
+    /// new [`BackendRef`]s are validated against ([`NumaStrategyConflictError::active`] stays the
+    /// strategy the backend was first created with) — only [`BackendRef::numa_strategy`] and
+    /// [`BackendRef::model_load_hints`] observe the change.
+    ///
+    /// Calling it with a different strategy after the backend's worker threads have already been
+    /// pinned to NUMA nodes (i.e. after a model has started doing work) is unsupported by
+    /// [ggml][ggml] and will not move already-pinned threads; it is only meaningful before any
+    /// inference has taken place.
+    ///
+    /// [llama.cpp]: https://github.com/ggerganov/llama.cpp/
+    /// [ggml]: https://github.com/ggerganov/ggml
+    pub(crate) fn reinit_numa(&self, numa: NumaStrategy) -> Result<(), BackendInitError> {
+        if numa == NumaStrategy::Count {
+            return Err(BackendInitError::InvalidStrategy(NumaStrategy::Count));
+        }
+
+        let mut lock = BACKEND.lock().unwrap();
+        let (backend, _) = lock
+            .as_mut()
+            .expect("a live BackendRef implies BACKEND is initialised");
+
+        if backend.current_numa == numa {
+            return Ok(());
+        }
+
+        unsafe {
+            // SAFETY: the backend this NUMA strategy applies to is still alive, guaranteed by
+            // `self` being a live `BackendRef`.
+            llama_numa_init(numa.into());
+        }
+        backend.current_numa = numa;
+        Ok(())
+    }
+
+    /// Returns the [`NumaStrategy`] the process-wide [`Backend`] is currently running with, which
+    /// may differ from the strategy it was first created with if [`BackendRef::reinit_numa`] has
+    /// been called since.
+    pub(crate) fn numa_strategy(&self) -> NumaStrategy {
+        let lock = BACKEND.lock().unwrap();
+        let (backend, _) = lock
+            .as_ref()
+            .expect("a live BackendRef implies BACKEND is initialised");
+        backend.current_numa
+    }
+
+    /// Returns the [`ModelLoadHints`] models should load with given the currently active
+    /// [`NumaStrategy`].
+    ///
+    /// Intended to be consulted wherever a model's `llama_model_params` are built, so that a
+    /// single [`NumaStrategy`] choice correctly configures both thread placement and
+    /// memory-mapping behaviour.
+    pub(crate) fn model_load_hints(&self) -> ModelLoadHints {
+        ModelLoadHints::from(self.numa_strategy())
+    }
+}
+
+#[cfg(test)]
+mod backend_ref_tests {
+    use super::*;
+
+    // These tests share the process-wide `BACKEND` singleton, so they run as a single test to
+    // avoid interfering with each other under cargo's default parallel test execution.
+    #[test]
+    fn numa_strategy_lifecycle() {
+        let first = BackendRef::with_numa(NumaStrategy::Isolate)
+            .expect("first BackendRef picks the strategy");
+        assert_eq!(first.numa_strategy(), NumaStrategy::Isolate);
+
+        // Requesting the same strategy while a reference is alive just joins it.
+        let _second = BackendRef::with_numa(NumaStrategy::Isolate)
+            .expect("matching strategy should not conflict");
+
+        // A conflicting strategy is rejected rather than silently ignored.
+        let conflict = BackendRef::with_numa(NumaStrategy::Distribute)
+            .expect_err("mismatched strategy should conflict");
+        match conflict {
+            BackendInitError::Conflict(conflict) => {
+                assert_eq!(conflict.active, NumaStrategy::Isolate);
+                assert_eq!(conflict.requested, NumaStrategy::Distribute);
+            }
+            BackendInitError::InvalidStrategy(_) => panic!("expected a conflict, not Count"),
+        }
+
+        // `NumaStrategy::Count` is ggml's internal sentinel, not a real strategy: reject it
+        // outright rather than forwarding it to `llama_numa_init`.
+        let invalid = BackendRef::with_numa(NumaStrategy::Count)
+            .expect_err("Count is not a selectable strategy");
+        assert!(matches!(
+            invalid,
+            BackendInitError::InvalidStrategy(NumaStrategy::Count)
+        ));
+
+        // `reinit_numa` changes what's currently active...
+        first
+            .reinit_numa(NumaStrategy::Mirror)
+            .expect("Mirror is a valid strategy");
+        assert_eq!(first.numa_strategy(), NumaStrategy::Mirror);
+
+        // ...but the conflict baseline new `BackendRef`s are checked against doesn't move.
+        let conflict_after_reinit = BackendRef::with_numa(NumaStrategy::Distribute)
+            .expect_err("reinit_numa must not redefine the conflict baseline");
+        assert!(matches!(
+            conflict_after_reinit,
+            BackendInitError::Conflict(NumaStrategyConflictError {
+                active: NumaStrategy::Isolate,
+                ..
+            })
+        ));
+
+        // `reinit_numa` to the strategy already active is a no-op, not an error.
+        first
+            .reinit_numa(NumaStrategy::Mirror)
+            .expect("re-requesting the active strategy is a no-op");
+        assert_eq!(first.numa_strategy(), NumaStrategy::Mirror);
+
+        // `reinit_numa` rejects `Count` too, without touching the active strategy.
+        first
+            .reinit_numa(NumaStrategy::Count)
+            .expect_err("Count is not a selectable strategy");
+        assert_eq!(first.numa_strategy(), NumaStrategy::Mirror);
+
+        // `model_load_hints` follows the active strategy (Mirror disables prefetch), and
+        // `BackendOptions` naming the original strategy still joins after `reinit_numa`.
+        assert!(!first.model_load_hints().prefetch);
+        let _third = BackendRef::with_options(BackendOptions {
+            numa: NumaStrategy::Isolate,
+            ..Default::default()
+        })
+        .expect("matches the original init_numa, so this still joins rather than conflicting");
+
+        // `new()` doesn't care which strategy is active; it always just joins.
+        let _fourth = BackendRef::new();
+    }
+}
+
+/// Hints for how a model should be loaded, derived from the active [`NumaStrategy`].
+///
+/// Exposed next to [`NumaStrategy`] so that model-loading code (where `use_mmap` and similar
+/// params on `llama_model_params` are set) has a single place to read NUMA-aware defaults from,
+/// rather than every caller having to reason about the interaction between NUMA pinning and
+/// memory-mapping themselves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ModelLoadHints {
+    /// Whether the model should be `mmap`'d at all. Always `true`; NUMA pinning only affects how
+    /// the mapping is read, not whether one is used.
+    pub use_mmap: bool,
+    /// Whether the OS should aggressively prefetch/readahead the mmap'd model file.
+    ///
+    /// Left `true` for [`NumaStrategy::Disable`]; for any other strategy, aggressive OS readahead
+    /// on an mmap'd file defeats the per-node locality NUMA pinning is trying to achieve, so this
+    /// is set to `false`, matching the upstream `ggml` NUMA patch's pairing of thread pinning with
+    /// disabled mmap prefetch.
+    pub prefetch: bool,
+}
+
+impl From<NumaStrategy> for ModelLoadHints {
+    fn from(numa: NumaStrategy) -> Self {
+        Self {
+            use_mmap: true,
+            prefetch: matches!(numa, NumaStrategy::Disable),
+        }
+    }
+}
+
+#[cfg(test)]
+mod model_load_hints_tests {
+    use super::*;
+
+    #[test]
+    fn disable_keeps_prefetch_enabled() {
+        let hints = ModelLoadHints::from(NumaStrategy::Disable);
+        assert!(hints.use_mmap);
+        assert!(hints.prefetch);
+    }
+
+    #[test]
+    fn any_other_strategy_disables_prefetch_but_keeps_mmap() {
+        for numa in [
+            NumaStrategy::Distribute,
+            NumaStrategy::Isolate,
+            NumaStrategy::Numactl,
+            NumaStrategy::Mirror,
+        ] {
+            let hints = ModelLoadHints::from(numa);
+            assert!(hints.use_mmap, "{numa:?} should not disable mmap");
+            assert!(!hints.prefetch, "{numa:?} should disable prefetch");
+        }
+    }
+}
+
+/// Best-effort count of NUMA nodes visible to this process.
+///
+/// [llama.cpp][llama.cpp] does not expose the node count `ggml_numa_init` detected through its
+/// public API, so this reads the same information the kernel exposes directly, mirroring what
+/// `numactl --hardware` reports. Returns `None` if that information isn't available (e.g. not
+/// running on Linux, or the running kernel has no NUMA support compiled in).
+///
+/// [llama.cpp]: https://github.com/ggerganov/llama.cpp/
+pub(crate) fn detected_numa_node_count() -> Option<usize> {
+    #[cfg(target_os = "linux")]
+    {
+        let entries = std::fs::read_dir("/sys/devices/system/node").ok()?;
+        let count = entries
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(is_numa_node_dir_name)
+            })
+            .count();
+        (count > 0).then_some(count)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Whether `name` is an entry `/sys/devices/system/node` uses for a NUMA node (`nodeN`, for some
+/// non-negative integer `N`), as opposed to one of the other files that directory also contains
+/// (e.g. `has_cpu`, `online`).
+#[cfg(target_os = "linux")]
+fn is_numa_node_dir_name(name: &str) -> bool {
+    name.strip_prefix("node")
+        .is_some_and(|rest| !rest.is_empty() && rest.bytes().all(|b| b.is_ascii_digit()))
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod numa_node_dir_name_tests {
+    use super::*;
+
+    #[test]
+    fn accepts_node_followed_by_digits() {
+        assert!(is_numa_node_dir_name("node0"));
+        assert!(is_numa_node_dir_name("node12"));
+    }
+
+    #[test]
+    fn rejects_unrelated_or_malformed_entries() {
+        assert!(!is_numa_node_dir_name("has_cpu"));
+        assert!(!is_numa_node_dir_name("online"));
+        assert!(!is_numa_node_dir_name("node"));
+        assert!(!is_numa_node_dir_name("nodeX"));
+        assert!(!is_numa_node_dir_name("anode0"));
+    }
+}
+
+/// Options controlling how the process-wide [`Backend`] is initialised.
+///
+/// Only meaningful for whichever [`BackendRef`] is first to initialise [`BACKEND`]; see
+/// [`BackendRef::with_options`].
+pub struct BackendOptions {
+    /// The [`NumaStrategy`] to initialise the backend with.
+    pub numa: NumaStrategy,
+    /// A custom sink for llama.cpp log messages, in place of the crate's built-in `tracing`
+    /// bridge. `None` keeps routing logs through `tracing`.
+    pub log: Option<LogConfig>,
+}
+
+impl Default for BackendOptions {
+    fn default() -> Self {
+        Self {
+            numa: NumaStrategy::Distribute,
+            log: None,
+        }
+    }
+}
+
+/// Routes llama.cpp log messages to a custom [`LogHandler`], dropping anything less severe than
+/// `min_level`.
+#[derive(Clone)]
+pub struct LogConfig {
+    /// The sink messages at or above `min_level` are forwarded to.
+    pub handler: LogHandler,
+    /// The least severe [`LogLevel`] that should still be forwarded to `handler`; anything less
+    /// severe (e.g. [`LogLevel::Debug`] when this is [`LogLevel::Info`]) is dropped before
+    /// `handler` is ever called.
+    pub min_level: LogLevel,
+}
+
+/// A sink for llama.cpp log messages, installed via [`LogConfig::handler`].
+pub type LogHandler = Arc<dyn Fn(LogLevel, &str) + Send + Sync>;
+
+/// Severity of a message logged by [llama.cpp][llama.cpp].
+///
+/// [llama.cpp]: https://github.com/ggerganov/llama.cpp/
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl From<ggml_log_level> for LogLevel {
+    fn from(value: ggml_log_level) -> Self {
+        #![allow(non_upper_case_globals)]
+        match value {
+            ggml_log_level::GGML_LOG_LEVEL_ERROR => LogLevel::Error,
+            ggml_log_level::GGML_LOG_LEVEL_WARN => LogLevel::Warn,
+            ggml_log_level::GGML_LOG_LEVEL_INFO => LogLevel::Info,
+            // `GGML_LOG_LEVEL_CONT` has no severity of its own (it continues whichever message
+            // came before it) and isn't meaningfully convertible in isolation; `log_trampoline`
+            // special-cases it via `LAST_LOG_LEVEL` instead of going through this conversion.
+            // Any other unrecognised value falls back to the least severe level.
+            _ => LogLevel::Debug,
+        }
+    }
+}
+
+/// The [`LogLevel`] of the last message [`log_trampoline`] forwarded, tracked so a following
+/// `GGML_LOG_LEVEL_CONT` chunk (which continues the previous line at *its* severity rather than
+/// being a new message) is filtered against the same `min_level` as the line it continues.
+static LAST_LOG_LEVEL: Mutex<LogLevel> = Mutex::new(LogLevel::Info);
+
+#[cfg(test)]
+mod log_level_tests {
+    use super::*;
+
+    #[test]
+    fn known_levels_map_directly() {
+        assert_eq!(
+            LogLevel::from(ggml_log_level::GGML_LOG_LEVEL_ERROR),
+            LogLevel::Error
+        );
+        assert_eq!(
+            LogLevel::from(ggml_log_level::GGML_LOG_LEVEL_WARN),
+            LogLevel::Warn
+        );
+        assert_eq!(
+            LogLevel::from(ggml_log_level::GGML_LOG_LEVEL_INFO),
+            LogLevel::Info
+        );
+    }
+
+    #[test]
+    fn ordering_runs_from_most_to_least_severe() {
+        assert!(LogLevel::Error < LogLevel::Warn);
+        assert!(LogLevel::Warn < LogLevel::Info);
+        assert!(LogLevel::Info < LogLevel::Debug);
+    }
+}
+
+/// The callback registered with `llama_log_set`.
+///
+/// Dispatches to the [`LogConfig`] in [`LOG_HANDLER`] if one has been installed and `level` is at
+/// least as severe as its `min_level`; messages less severe than that are dropped. Falls back to
+/// the crate's built-in `tracing` bridge only when no [`LogConfig`] is installed.
+extern "C" fn log_trampoline(level: ggml_log_level, text: *const c_char, user_data: *mut c_void) {
+    // Clone the config out and drop the guard before calling the handler: the handler may itself
+    // trigger llama.cpp logging (re-entering this very function on the same thread) or panic,
+    // either of which would deadlock or poison `LOG_HANDLER` if we were still holding the lock.
+    let config = LOG_HANDLER.lock().unwrap().clone();
+
+    // `GGML_LOG_LEVEL_CONT` continues the previous message at its severity rather than starting
+    // a new one, so it's filtered against `LAST_LOG_LEVEL` instead of a fresh conversion (which
+    // would otherwise treat it as the least severe level and drop it under a strict `min_level`).
+    let mapped_level = if level == ggml_log_level::GGML_LOG_LEVEL_CONT {
+        *LAST_LOG_LEVEL.lock().unwrap()
+    } else {
+        let mapped_level = LogLevel::from(level);
+        *LAST_LOG_LEVEL.lock().unwrap() = mapped_level;
+        mapped_level
+    };
+
+    match config {
+        Some(config) if !text.is_null() && mapped_level <= config.min_level => {
+            // SAFETY: llama.cpp always passes a valid, NUL-terminated C string here.
+            let text = unsafe { CStr::from_ptr(text) }.to_string_lossy();
+            (config.handler)(mapped_level, &text);
+        }
+        Some(_) => {
+            // A handler is installed, but this message is below its `min_level`: drop it rather
+            // than falling back to `tracing`.
+        }
+        None => {
+            // SAFETY: same contract as the callback this replaces.
+            unsafe { detail::llama_log_callback(level, text, user_data) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod log_trampoline_tests {
+    use super::*;
+    use std::ffi::CString;
+
+    // `log_trampoline` reads and writes `LOG_HANDLER`/`LAST_LOG_LEVEL`, both process-wide, so this
+    // is a single test exercising the whole sequence rather than several that could interleave
+    // under cargo's default parallel test execution.
+    #[test]
+    fn cont_inherits_the_previous_messages_level() {
+        let captured = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&captured);
+        *LOG_HANDLER.lock().unwrap() = Some(LogConfig {
+            handler: Arc::new(move |level, text| {
+                sink.lock().unwrap().push((level, text.to_string()));
+            }),
+            min_level: LogLevel::Warn,
+        });
+
+        let error_line = CString::new("boom").unwrap();
+        let continuation = CString::new(" (more detail)").unwrap();
+        log_trampoline(
+            ggml_log_level::GGML_LOG_LEVEL_ERROR,
+            error_line.as_ptr(),
+            ptr::null_mut(),
+        );
+        log_trampoline(
+            ggml_log_level::GGML_LOG_LEVEL_CONT,
+            continuation.as_ptr(),
+            ptr::null_mut(),
+        );
+
+        let got = captured.lock().unwrap();
+        assert_eq!(
+            got.as_slice(),
+            [
+                (LogLevel::Error, "boom".to_string()),
+                (LogLevel::Error, " (more detail)".to_string()),
+            ],
+            "the CONT chunk should inherit Error's severity and pass the Warn min_level filter"
+        );
+        drop(got);
+
+        // An Info line followed by CONT should have both dropped under a Warn `min_level`.
+        let info_line = CString::new("starting up").unwrap();
+        let info_continuation = CString::new(" ...").unwrap();
+        log_trampoline(
+            ggml_log_level::GGML_LOG_LEVEL_INFO,
+            info_line.as_ptr(),
+            ptr::null_mut(),
+        );
+        log_trampoline(
+            ggml_log_level::GGML_LOG_LEVEL_CONT,
+            info_continuation.as_ptr(),
+            ptr::null_mut(),
+        );
+        assert_eq!(
+            captured.lock().unwrap().len(),
+            2,
+            "Info and its CONT should both be dropped"
+        );
+
+        LOG_HANDLER.lock().unwrap().take();
     }
 }
 
@@ -94,12 +633,21 @@ impl Drop for BackendRef {
 
 impl Clone for BackendRef {
     fn clone(&self) -> Self {
-        Self::new()
+        // `self` being alive guarantees `BACKEND` is already initialised, so this can just bump
+        // the refcount directly rather than going through `BackendRef::new`'s join-or-init path.
+        let mut lock = BACKEND.lock().unwrap();
+        let (_, count) = lock
+            .as_mut()
+            .expect("a live BackendRef implies BACKEND is initialised");
+        *count += 1;
+
+        Self {}
     }
 }
 
 /// A policy to split the model across multiple GPUs
 #[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum NumaStrategy {
     Disable,
     Distribute,
@@ -136,3 +684,69 @@ impl From<ggml_numa_strategy> for NumaStrategy {
         }
     }
 }
+
+/// Returned, inside [`BackendInitError::Conflict`], by [`BackendRef::with_numa`] and
+/// [`BackendRef::with_options`] when the process-wide [`BACKEND`] was already initialised with a
+/// [`NumaStrategy`] other than the one requested.
+///
+/// [`BACKEND`] is only ever initialised once per process, so whichever [`BackendRef`] gets there
+/// first decides the strategy every other [`BackendRef`] is checked against for the lifetime of
+/// the process. This baseline is unaffected by [`BackendRef::reinit_numa`]: that only changes
+/// which strategy is *currently* active ([`BackendRef::numa_strategy`]), not the one conflicts are
+/// reported against.
+#[derive(Debug)]
+pub struct NumaStrategyConflictError {
+    /// The [`NumaStrategy`] the backend was first initialised with.
+    pub active: NumaStrategy,
+    /// The [`NumaStrategy`] that was requested.
+    pub requested: NumaStrategy,
+}
+
+impl fmt::Display for NumaStrategyConflictError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "llama.cpp backend is already initialised with NUMA strategy {:?}, cannot switch to {:?}",
+            self.active, self.requested
+        )
+    }
+}
+
+impl std::error::Error for NumaStrategyConflictError {}
+
+/// Returned by [`BackendRef::with_numa`], [`BackendRef::with_options`] and
+/// [`BackendRef::reinit_numa`] when the requested [`NumaStrategy`] cannot be used.
+#[derive(Debug)]
+pub enum BackendInitError {
+    /// The process-wide [`BACKEND`] was already initialised with a different [`NumaStrategy`].
+    Conflict(NumaStrategyConflictError),
+    /// The requested [`NumaStrategy`] isn't a real strategy that can be passed to
+    /// `llama_numa_init` ([`NumaStrategy::Count`] is ggml's internal element-count sentinel).
+    InvalidStrategy(NumaStrategy),
+}
+
+impl fmt::Display for BackendInitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackendInitError::Conflict(err) => fmt::Display::fmt(err, f),
+            BackendInitError::InvalidStrategy(numa) => {
+                write!(f, "{numa:?} is not a selectable NUMA strategy")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BackendInitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BackendInitError::Conflict(err) => Some(err),
+            BackendInitError::InvalidStrategy(_) => None,
+        }
+    }
+}
+
+impl From<NumaStrategyConflictError> for BackendInitError {
+    fn from(err: NumaStrategyConflictError) -> Self {
+        BackendInitError::Conflict(err)
+    }
+}
@@ -0,0 +1,193 @@
+//! Implements the [`LlamaModel`] struct, an owned handle to a llama.cpp model loaded from disk.
+
+mod backend;
+
+use std::ffi::CString;
+use std::fmt;
+use std::path::Path;
+use std::ptr::NonNull;
+
+use llama_cpp_sys::{
+    llama_free_model, llama_load_model_from_file, llama_model, llama_model_default_params,
+};
+
+use backend::BackendRef;
+pub use backend::{
+    BackendInitError, BackendOptions, LogConfig, LogHandler, LogLevel, ModelLoadHints,
+    NumaStrategy, NumaStrategyConflictError,
+};
+
+/// Parameters controlling how a [`LlamaModel`] is loaded.
+///
+/// This is the public surface for picking a process-wide [`NumaStrategy`] and log sink: the first
+/// [`LlamaModel`] loaded in a process decides both for [`BackendRef`]'s lifetime. Later loads must
+/// ask for the same [`NumaStrategy`] or fail with [`LlamaLoadError::Backend`]; their `log` is
+/// ignored.
+pub struct LlamaParams {
+    /// The [`NumaStrategy`] to initialise the backend with, if it isn't already running.
+    pub numa: NumaStrategy,
+    /// A custom sink for llama.cpp log messages. `None` keeps routing logs through `tracing`.
+    pub log: Option<LogConfig>,
+}
+
+impl Default for LlamaParams {
+    fn default() -> Self {
+        Self {
+            numa: NumaStrategy::Distribute,
+            log: None,
+        }
+    }
+}
+
+/// An owned handle to a llama.cpp model loaded from disk.
+///
+/// Holds a [`BackendRef`] for as long as the model is alive, so the process-wide backend isn't
+/// torn down while this model could still be used to create a session.
+pub struct LlamaModel {
+    handle: NonNull<llama_model>,
+    #[allow(dead_code)]
+    backend: BackendRef,
+}
+
+// SAFETY: `llama_model` is only ever read from and freed through `*mut llama_model`-taking
+// llama.cpp functions, none of which llama.cpp documents as requiring external synchronisation.
+unsafe impl Send for LlamaModel {}
+unsafe impl Sync for LlamaModel {}
+
+impl LlamaModel {
+    /// Loads a model from `path` with the default [`LlamaParams`].
+    pub fn load_from_file(path: impl AsRef<Path>) -> Result<Self, LlamaLoadError> {
+        Self::load_from_file_with_params(path, LlamaParams::default())
+    }
+
+    /// Loads a model from `path`, initialising the process-wide backend with `params.numa` and
+    /// `params.log` if it isn't already running.
+    pub fn load_from_file_with_params(
+        path: impl AsRef<Path>,
+        params: LlamaParams,
+    ) -> Result<Self, LlamaLoadError> {
+        let backend = BackendRef::with_options(BackendOptions {
+            numa: params.numa,
+            log: params.log,
+        })?;
+
+        let hints = backend.model_load_hints();
+
+        let path = path.as_ref();
+        let path_cstr = CString::new(path.to_string_lossy().into_owned())
+            .map_err(|_| LlamaLoadError::InvalidPath)?;
+
+        if !hints.prefetch {
+            readahead::disable(path);
+        }
+
+        let mut model_params = unsafe {
+            // SAFETY: returns a plain-old-data struct of FFI defaults; no preconditions.
+            llama_model_default_params()
+        };
+        model_params.use_mmap = hints.use_mmap;
+
+        let handle = unsafe {
+            // SAFETY: `path_cstr` is a valid, NUL-terminated C string for the duration of this
+            // call; `model_params` is a valid `llama_model_params` obtained just above.
+            llama_load_model_from_file(path_cstr.as_ptr(), model_params)
+        };
+        let handle = NonNull::new(handle).ok_or(LlamaLoadError::LoadFailed)?;
+
+        Ok(Self { handle, backend })
+    }
+}
+
+impl Drop for LlamaModel {
+    fn drop(&mut self) {
+        unsafe {
+            // SAFETY: `self.handle` was returned by `llama_load_model_from_file` and hasn't been
+            // freed yet, guaranteed by this being the only place `LlamaModel` frees it.
+            llama_free_model(self.handle.as_ptr());
+        }
+    }
+}
+
+/// An error encountered while loading a [`LlamaModel`].
+#[derive(Debug)]
+pub enum LlamaLoadError {
+    /// The process-wide backend could not be initialised or joined with the requested
+    /// [`NumaStrategy`]. See [`BackendInitError`].
+    Backend(BackendInitError),
+    /// `path` could not be represented as a C string (it contained a NUL byte).
+    InvalidPath,
+    /// llama.cpp returned a null model handle, without further detail as to why.
+    LoadFailed,
+}
+
+impl From<BackendInitError> for LlamaLoadError {
+    fn from(err: BackendInitError) -> Self {
+        LlamaLoadError::Backend(err)
+    }
+}
+
+impl fmt::Display for LlamaLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LlamaLoadError::Backend(err) => fmt::Display::fmt(err, f),
+            LlamaLoadError::InvalidPath => write!(f, "model path contained a NUL byte"),
+            LlamaLoadError::LoadFailed => write!(f, "llama.cpp failed to load the model"),
+        }
+    }
+}
+
+impl std::error::Error for LlamaLoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LlamaLoadError::Backend(err) => Some(err),
+            LlamaLoadError::InvalidPath | LlamaLoadError::LoadFailed => None,
+        }
+    }
+}
+
+/// Disables OS readahead on model files, to satisfy [`ModelLoadHints::prefetch`].
+///
+/// `llama_model_params` has no field for this: llama.cpp's own NUMA patch achieves it by calling
+/// `posix_fadvise(..., POSIX_FADV_RANDOM)` on the model file before `mmap`-ing it, so this does the
+/// same rather than silently ignoring the hint.
+mod readahead {
+    use std::path::Path;
+
+    /// Best-effort: a failure to open `path` or to apply the advice is not reported, since the
+    /// model load that follows will surface any real problem with `path` itself, and prefetching
+    /// is only ever a performance hint in the first place.
+    #[cfg(unix)]
+    pub(super) fn disable(path: &Path) {
+        use std::fs::File;
+        use std::os::unix::io::AsRawFd;
+
+        if let Ok(file) = File::open(path) {
+            unsafe {
+                // SAFETY: `file`'s descriptor is open and valid for the duration of this call.
+                // `offset`/`len` of 0 means "the whole file", per `posix_fadvise(3)`.
+                fadvise::posix_fadvise(file.as_raw_fd(), 0, 0, fadvise::POSIX_FADV_RANDOM);
+            }
+        }
+    }
+
+    /// No equivalent readahead control is wired up outside Unix; the hint is silently ignored
+    /// rather than failing the load over a performance-only setting.
+    #[cfg(not(unix))]
+    pub(super) fn disable(_path: &Path) {}
+
+    #[cfg(unix)]
+    mod fadvise {
+        use std::os::raw::{c_int, c_longlong};
+
+        pub(super) const POSIX_FADV_RANDOM: c_int = 1;
+
+        extern "C" {
+            pub(super) fn posix_fadvise(
+                fd: c_int,
+                offset: c_longlong,
+                len: c_longlong,
+                advice: c_int,
+            ) -> c_int;
+        }
+    }
+}